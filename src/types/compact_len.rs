@@ -0,0 +1,90 @@
+//! Shared SCALE-style compact length encoding used by the `Compact*` prefix
+//! types.
+//!
+//! The two least-significant bits of the first byte select a mode:
+//!
+//! * `0b00` - single byte, length is `byte >> 2` (0..=63)
+//! * `0b01` - two bytes (little-endian), length is `(u16 >> 2)` (64..=16383)
+//! * `0b10` - four bytes (little-endian), length is `(u32 >> 2)` (16384..=2^30-1)
+//! * `0b11` - big-integer mode: the remaining 6 bits of the first byte hold
+//!   `number_of_following_bytes - 4`, and those following bytes are the
+//!   little-endian length.
+
+use borsh::maybestd::io::{Error, ErrorKind, Read, Result, Write};
+
+const SINGLE_BYTE_MAX: usize = 0x3f; // 63
+const TWO_BYTE_MAX: usize = 0x3fff; // 16_383
+const FOUR_BYTE_MAX: usize = 0x3fff_ffff; // 2^30 - 1
+
+/// Encodes `length` as a compact length prefix.
+pub(crate) fn encode(length: usize) -> Vec<u8> {
+    if length <= SINGLE_BYTE_MAX {
+        vec![(length as u8) << 2]
+    } else if length <= TWO_BYTE_MAX {
+        let value = ((length as u16) << 2) | 0b01;
+        value.to_le_bytes().to_vec()
+    } else if length <= FOUR_BYTE_MAX {
+        let value = ((length as u32) << 2) | 0b10;
+        value.to_le_bytes().to_vec()
+    } else {
+        let bytes = length.to_le_bytes();
+        // number of bytes actually needed to represent `length`
+        let needed = bytes
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(1, |i| i + 1)
+            .max(4);
+        let first = (((needed - 4) as u8) << 2) | 0b11;
+
+        let mut out = Vec::with_capacity(1 + needed);
+        out.push(first);
+        out.extend_from_slice(&bytes[..needed]);
+        out
+    }
+}
+
+/// Writes a compact length prefix to `writer`.
+pub(crate) fn write<W: Write>(length: usize, writer: &mut W) -> Result<()> {
+    writer.write_all(&encode(length))
+}
+
+/// Reads a compact length prefix from `reader`.
+pub(crate) fn read<R: Read>(reader: &mut R) -> Result<usize> {
+    let mut first = [0u8; 1];
+    reader.read_exact(&mut first)?;
+    let first = first[0];
+
+    let length = match first & 0b11 {
+        0b00 => (first >> 2) as usize,
+        0b01 => {
+            let mut rest = [0u8; 1];
+            reader.read_exact(&mut rest)?;
+            let value = u16::from_le_bytes([first, rest[0]]);
+            (value >> 2) as usize
+        }
+        0b10 => {
+            let mut rest = [0u8; 3];
+            reader.read_exact(&mut rest)?;
+            let value = u32::from_le_bytes([first, rest[0], rest[1], rest[2]]);
+            (value >> 2) as usize
+        }
+        _ => {
+            let needed = (first >> 2) as usize + 4;
+            if needed > std::mem::size_of::<usize>() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "compact length does not fit in a usize",
+                ));
+            }
+
+            let mut rest = vec![0u8; needed];
+            reader.read_exact(&mut rest)?;
+
+            let mut bytes = [0u8; std::mem::size_of::<usize>()];
+            bytes[..needed].copy_from_slice(&rest);
+            usize::from_le_bytes(bytes)
+        }
+    };
+
+    Ok(length)
+}
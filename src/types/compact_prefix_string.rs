@@ -0,0 +1,137 @@
+use borsh::maybestd::io::{Error, ErrorKind, Read, Result};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::fmt::Debug;
+use std::io::Write;
+use std::ops::Deref;
+
+use crate::types::compact_len;
+use crate::types::serialized_len::SerializedLen;
+
+/// Upper bound on how many bytes are read per chunk while filling the
+/// string buffer for a declared (and as yet unverified) compact length.
+const MAX_PREALLOCATION: usize = 4096;
+
+/// A string prefixed by a SCALE-style compact length, so short strings cost
+/// a single byte while long ones still round-trip.
+///
+/// See [`compact_len`](crate::types::compact_len) for the encoding used for
+/// the length prefix.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompactPrefixString(String);
+
+/// Deferences the inner `String` type.
+impl Deref for CompactPrefixString {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// `Debug` implementation.
+///
+/// This implementation simply forwards to the inner `String` type.
+impl Debug for CompactPrefixString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.0))
+    }
+}
+
+impl BorshDeserialize for CompactPrefixString {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let length = compact_len::read(reader)?;
+
+        // `compact_len::read` can decode a length up to `usize::MAX` from as
+        // little as a handful of bytes (big-integer mode), so the declared
+        // length cannot be trusted as an allocation size: read (and grow the
+        // buffer) in bounded chunks instead of zero-filling the whole thing
+        // upfront.
+        let mut buffer = Vec::with_capacity(length.min(MAX_PREALLOCATION));
+        let mut remaining = length;
+        let mut chunk = [0u8; MAX_PREALLOCATION];
+        while remaining > 0 {
+            let chunk_len = remaining.min(MAX_PREALLOCATION);
+            reader.read_exact(&mut chunk[..chunk_len])?;
+            buffer.extend_from_slice(&chunk[..chunk_len]);
+            remaining -= chunk_len;
+        }
+
+        Ok(Self(
+            String::from_utf8(buffer).map_err(|_| Error::new(ErrorKind::InvalidData, "invalid utf8"))?,
+        ))
+    }
+}
+
+impl BorshSerialize for CompactPrefixString {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        // add the compact length prefix
+        compact_len::write(self.0.len(), writer)?;
+        // serialize the string (without its "natural" prefix)
+        writer.write_all(self.0.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+impl SerializedLen for CompactPrefixString {
+    // the compact length prefix is at least a single byte; it grows for
+    // larger lengths, so `serialized_len` below computes the exact prefix
+    // size rather than relying on this constant
+    const PREFIX_LEN: usize = 1;
+
+    fn serialized_len(&self) -> usize {
+        compact_len::encode(self.0.len()).len() + self.0.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_short_and_long_strings() {
+        for length in [0usize, 1, 63, 64, 16_383, 16_384] {
+            let string = "a".repeat(length);
+            let source = CompactPrefixString(string);
+
+            let mut data = Vec::new();
+            source.serialize(&mut data).unwrap();
+
+            let restored = CompactPrefixString::try_from_slice(&data).unwrap();
+            assert_eq!(*restored, *source);
+        }
+    }
+
+    #[test]
+    fn fail_deserialize_declared_length_too_long() {
+        // compact length of 3, but no bytes follow
+        let data = [3u8 << 2];
+
+        let error = CompactPrefixString::try_from_slice(&data).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn fail_deserialize_does_not_trust_a_huge_declared_length() {
+        // big-integer mode declaring a length of `usize::MAX` from a
+        // handful of bytes; the reader holds none of that data, so this
+        // must fail fast rather than attempt a huge zero-filled allocation.
+        let encoded = compact_len::encode(usize::MAX);
+
+        let error = CompactPrefixString::try_from_slice(&encoded).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        for length in [0usize, 63, 64, 16_384] {
+            let source = CompactPrefixString("a".repeat(length));
+
+            let mut data = Vec::new();
+            source.serialize(&mut data).unwrap();
+
+            assert_eq!(source.serialized_len(), data.len());
+        }
+    }
+}
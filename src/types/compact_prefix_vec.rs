@@ -0,0 +1,185 @@
+use std::fmt::Debug;
+use std::io::Write;
+use std::ops::{Deref, DerefMut};
+
+use borsh::maybestd::io::Read;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::types::compact_len;
+use crate::types::serialized_len::{borsh_len, SerializedLen};
+
+/// Upper bound on how many elements are pre-allocated based on a declared
+/// (and as yet unverified) compact length.
+const MAX_PREALLOCATION: usize = 4096;
+
+/// A vector where the element data is prefixed by a SCALE-style compact
+/// length, so short vectors cost a single byte while long ones still
+/// round-trip.
+///
+/// See [`compact_len`](crate::types::compact_len) for the encoding used for
+/// the length prefix.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompactPrefixVec<T: BorshSerialize + BorshDeserialize>(Vec<T>);
+
+/// Deferences the inner `Vec` type.
+impl<T> Deref for CompactPrefixVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Deferences the inner `Vec` type as mutable.
+impl<T> DerefMut for CompactPrefixVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// `Debug` implementation.
+///
+/// This implementation simply forwards to the inner `Vec` type.
+impl<T> Debug for CompactPrefixVec<T>
+where
+    T: BorshSerialize + BorshDeserialize + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.0))
+    }
+}
+
+impl<T> BorshDeserialize for CompactPrefixVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::maybestd::io::Result<Self> {
+        let length = compact_len::read(reader)?;
+
+        // `compact_len::read` can decode a length up to `usize::MAX` from as
+        // little as a handful of bytes (big-integer mode), so the declared
+        // length cannot be trusted as a pre-allocation size: cap the initial
+        // capacity and let the `Vec` grow as elements are actually read.
+        let mut items: Vec<T> = Vec::with_capacity(length.min(MAX_PREALLOCATION));
+        for _ in 0..length {
+            items.push(T::deserialize_reader(reader)?);
+        }
+
+        Ok(Self(items))
+    }
+}
+
+impl<T> BorshSerialize for CompactPrefixVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn serialize<W: Write>(&self, writer: &mut W) -> borsh::maybestd::io::Result<()> {
+        // add the compact length prefix
+        compact_len::write(self.0.len(), writer)?;
+        // serialize each item
+        for item in self.0.iter() {
+            item.serialize(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> SerializedLen for CompactPrefixVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    // the compact length prefix is at least a single byte; it grows for
+    // larger lengths, so `serialized_len` below computes the exact prefix
+    // size rather than relying on this constant
+    const PREFIX_LEN: usize = 1;
+
+    fn serialized_len(&self) -> usize {
+        compact_len::encode(self.0.len()).len()
+            + self.0.iter().map(|item| borsh_len(item)).sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_vec() {
+        for &length in &[0usize, 1, 63, 64, 16_383, 16_384] {
+            let values = vec![7u8; length];
+            let source = CompactPrefixVec(values);
+
+            let mut data = Vec::new();
+            source.serialize(&mut data).unwrap();
+
+            let restored = CompactPrefixVec::<u8>::try_from_slice(&data).unwrap();
+            assert_eq!(restored.as_slice(), source.as_slice());
+        }
+    }
+
+    #[test]
+    fn mode_selection_at_boundaries() {
+        // (length, expected mode)
+        let cases = [
+            (0usize, 0b00u8),
+            (63, 0b00),
+            (64, 0b01),
+            (16_383, 0b01),
+            (16_384, 0b10),
+            (1 << 30, 0b11),
+        ];
+
+        for (length, mode) in cases {
+            let encoded = compact_len::encode(length);
+            assert_eq!(encoded[0] & 0b11, mode, "length {length}");
+
+            let decoded = compact_len::read(&mut encoded.as_slice()).unwrap();
+            assert_eq!(decoded, length);
+        }
+    }
+
+    #[test]
+    fn fail_deserialize_declared_length_too_long() {
+        // compact length of 3, but no elements follow. borsh's own
+        // primitive deserializers remap a truncated read to `InvalidInput`,
+        // not `UnexpectedEof`.
+        let data = [3u8 << 2];
+
+        let error = CompactPrefixVec::<u64>::try_from_slice(&data).unwrap_err();
+        assert_eq!(error.kind(), borsh::maybestd::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        for &length in &[0usize, 63, 64, 16_384] {
+            let values = vec![7u8; length];
+            let source = CompactPrefixVec(values);
+
+            let mut data = Vec::new();
+            source.serialize(&mut data).unwrap();
+
+            assert_eq!(source.serialized_len(), data.len());
+        }
+    }
+
+    #[test]
+    fn fail_deserialize_does_not_trust_a_huge_declared_length() {
+        // big-integer mode declaring a length of `usize::MAX` from a
+        // handful of bytes; the reader holds none of that data, so this
+        // must fail fast rather than attempt a huge pre-allocation. borsh's
+        // own primitive deserializers remap a truncated read to
+        // `InvalidInput`, not `UnexpectedEof`.
+        let encoded = compact_len::encode(usize::MAX);
+
+        let error = CompactPrefixVec::<u8>::try_from_slice(&encoded).unwrap_err();
+        assert_eq!(error.kind(), borsh::maybestd::io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "heapless")]
+
+//! `no_std`, fixed-capacity versions of the
+//! [`PrefixString`](crate::types::prefix_string) family, backed by
+//! [`heapless::String`] instead of the heap-allocated `String`.
+//!
+//! The wire format is identical to the heap-allocated variants, so the two
+//! families are wire-compatible; only the in-memory representation and
+//! capacity handling differ.
+
+use core::fmt::Debug;
+use core::ops::Deref;
+
+use borsh::maybestd::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use heapless::String as HeaplessString;
+
+use crate::types::serialized_len::SerializedLen;
+
+/// Macro to automate the generation of `HeaplessPrefixString` types.
+macro_rules! heapless_prefix_string_types {
+    ( ($n:tt, $p:tt), $(($name:tt, $prefix:tt)),+ ) => {
+        heapless_prefix_string_types!(($n, $p));
+        heapless_prefix_string_types!($( ($name, $prefix) ),+);
+    };
+    ( ($name:tt, $prefix_type:tt) ) => {
+        /// A fixed-capacity string prefixed by a "custom" length type.
+        ///
+        /// Deserialization fails (instead of allocating) when the decoded
+        /// length exceeds the compile-time capacity `N`.
+        #[derive(Clone, Eq, PartialEq)]
+        pub struct $name<const N: usize>(HeaplessString<N>);
+
+        /// Deferences the inner `heapless::String` type.
+        impl<const N: usize> Deref for $name<N> {
+            type Target = HeaplessString<N>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        /// `Debug` implementation.
+        ///
+        /// This implementation simply forwards to the inner
+        /// `heapless::String` type.
+        impl<const N: usize> Debug for $name<N> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_fmt(format_args!("{:?}", self.0))
+            }
+        }
+
+        impl<const N: usize> BorshDeserialize for $name<N> {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                // read the length of the String
+                let mut buffer = [0u8; core::mem::size_of::<$prefix_type>()];
+                reader.read_exact(&mut buffer)?;
+                let length = $prefix_type::from_le_bytes(buffer) as usize;
+
+                if length > N {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "declared length exceeds heapless capacity",
+                    ));
+                }
+
+                let mut bytes: heapless::Vec<u8, N> = heapless::Vec::new();
+                bytes
+                    .resize(length, 0)
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, "capacity exceeded"))?;
+                reader.read_exact(&mut bytes)?;
+
+                let string = HeaplessString::from_utf8(bytes)
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, "invalid utf8"))?;
+
+                Ok(Self(string))
+            }
+        }
+
+        impl<const N: usize> BorshSerialize for $name<N> {
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                if self.0.len() > $prefix_type::MAX as usize {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "size of string too big for prefix type",
+                    ));
+                }
+                // add the length prefix
+                writer.write_all(&$prefix_type::to_le_bytes(self.0.len() as $prefix_type))?;
+                // serialize the string (without its "natural" prefix)
+                writer.write_all(self.0.as_bytes())?;
+
+                Ok(())
+            }
+        }
+
+        impl<const N: usize> SerializedLen for $name<N> {
+            const PREFIX_LEN: usize = core::mem::size_of::<$prefix_type>();
+
+            fn serialized_len(&self) -> usize {
+                Self::PREFIX_LEN + self.0.len()
+            }
+        }
+    };
+}
+
+heapless_prefix_string_types!(
+    (HeaplessU8PrefixString, u8),
+    (HeaplessU16PrefixString, u16)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_data() {
+        let source = HeaplessU8PrefixString::<8>(HeaplessString::try_from("string").unwrap());
+
+        let mut data = [0u8; 7];
+        source.serialize(&mut data.as_mut_slice()).unwrap();
+
+        let restored = HeaplessU8PrefixString::<8>::try_from_slice(&data).unwrap();
+
+        assert_eq!(*restored, *source);
+    }
+
+    #[test]
+    fn fail_deserialize_length_exceeds_capacity() {
+        // declares a length of 6, but capacity is only 4
+        let mut data = [0u8; 7];
+        data[0] = 6;
+        data[1..7].copy_from_slice("string".as_bytes());
+
+        let error = HeaplessU8PrefixString::<4>::try_from_slice(&data).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        let source = HeaplessU8PrefixString::<8>(HeaplessString::try_from("string").unwrap());
+
+        let mut data = [0u8; 7];
+        source.serialize(&mut data.as_mut_slice()).unwrap();
+
+        assert_eq!(source.serialized_len(), data.len());
+        assert_eq!(HeaplessU8PrefixString::<8>::PREFIX_LEN, 1);
+    }
+}
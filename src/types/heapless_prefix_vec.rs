@@ -0,0 +1,201 @@
+#![cfg(feature = "heapless")]
+
+//! `no_std`, fixed-capacity versions of the [`PrefixVec`](crate::types::prefix_vec)
+//! family, backed by [`heapless::Vec`] instead of the heap-allocated `Vec`.
+//!
+//! The wire format is identical to the heap-allocated variants (a
+//! little-endian length prefix followed by the borsh-serialized elements),
+//! so the two families are wire-compatible; only the in-memory
+//! representation and capacity handling differ.
+
+use core::fmt::Debug;
+use core::ops::{Deref, DerefMut};
+
+use borsh::maybestd::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use heapless::Vec as HeaplessVec;
+
+use crate::types::serialized_len::SerializedLen;
+
+/// A `Write` sink that only counts the bytes written to it, used to measure
+/// an item's serialized size without allocating a buffer for it.
+struct CountingWriter(usize);
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Macro to automate the generation of `HeaplessPrefixVec` types.
+macro_rules! heapless_prefix_vec_types {
+    ( ($n:tt, $p:tt), $(($name:tt, $prefix:tt)),+ ) => {
+        heapless_prefix_vec_types!(($n, $p));
+        heapless_prefix_vec_types!($( ($name, $prefix) ),+);
+    };
+    ( ($name:tt, $prefix_type:tt) ) => {
+        /// A fixed-capacity vector where the element data is prefixed by the
+        /// vector length.
+        ///
+        /// Deserialization fails (instead of allocating) when the decoded
+        /// length exceeds the compile-time capacity `N`.
+        #[derive(Clone, Eq, PartialEq)]
+        pub struct $name<T: BorshSerialize + BorshDeserialize, const N: usize>(HeaplessVec<T, N>);
+
+        /// Deferences the inner `heapless::Vec` type.
+        impl<T, const N: usize> Deref for $name<T, N>
+        where
+            T: BorshSerialize + BorshDeserialize,
+        {
+            type Target = HeaplessVec<T, N>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        /// Deferences the inner `heapless::Vec` type as mutable.
+        impl<T, const N: usize> DerefMut for $name<T, N>
+        where
+            T: BorshSerialize + BorshDeserialize,
+        {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        /// `Debug` implementation.
+        ///
+        /// This implementation simply forwards to the inner `heapless::Vec`
+        /// type.
+        impl<T, const N: usize> Debug for $name<T, N>
+        where
+            T: BorshSerialize + BorshDeserialize + Debug,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_fmt(format_args!("{:?}", self.0))
+            }
+        }
+
+        impl<T, const N: usize> BorshDeserialize for $name<T, N>
+        where
+            T: BorshSerialize + BorshDeserialize,
+        {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                // read the length of the vec
+                let mut buffer = [0u8; core::mem::size_of::<$prefix_type>()];
+                reader.read_exact(&mut buffer)?;
+                let length = $prefix_type::from_le_bytes(buffer) as usize;
+
+                if length > N {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "declared length exceeds heapless capacity",
+                    ));
+                }
+
+                let mut items: HeaplessVec<T, N> = HeaplessVec::new();
+                for _ in 0..length {
+                    // never fails: `length <= N` was checked above
+                    items
+                        .push(T::deserialize_reader(reader)?)
+                        .map_err(|_| Error::new(ErrorKind::InvalidData, "capacity exceeded"))?;
+                }
+
+                Ok(Self(items))
+            }
+        }
+
+        impl<T, const N: usize> BorshSerialize for $name<T, N>
+        where
+            T: BorshSerialize + BorshDeserialize,
+        {
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                if self.0.len() > $prefix_type::MAX as usize {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "size of vec too big for prefix type",
+                    ));
+                }
+                // add the length prefix
+                writer.write_all(&$prefix_type::to_le_bytes(self.0.len() as $prefix_type))?;
+                // serialize each item
+                for item in self.0.iter() {
+                    item.serialize(writer)?;
+                }
+
+                Ok(())
+            }
+        }
+
+        impl<T, const N: usize> SerializedLen for $name<T, N>
+        where
+            T: BorshSerialize + BorshDeserialize,
+        {
+            const PREFIX_LEN: usize = core::mem::size_of::<$prefix_type>();
+
+            fn serialized_len(&self) -> usize {
+                let mut writer = CountingWriter(0);
+                for item in self.0.iter() {
+                    item.serialize(&mut writer)
+                        .expect("serializing into a counting writer cannot fail");
+                }
+                Self::PREFIX_LEN + writer.0
+            }
+        }
+    };
+}
+
+heapless_prefix_vec_types!(
+    (HeaplessU8PrefixVec, u8),
+    (HeaplessU16PrefixVec, u16),
+    (HeaplessU32PrefixVec, u32)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_data() {
+        let mut values: HeaplessVec<u32, 4> = HeaplessVec::new();
+        values.extend([1, 2, 3]);
+        let source = HeaplessU8PrefixVec::<u32, 4>(values);
+
+        let mut data = [0u8; 13];
+        source.serialize(&mut data.as_mut_slice()).unwrap();
+
+        let restored = HeaplessU8PrefixVec::<u32, 4>::try_from_slice(&data).unwrap();
+
+        assert_eq!(restored.as_slice(), source.as_slice());
+    }
+
+    #[test]
+    fn fail_deserialize_length_exceeds_capacity() {
+        // declares a length of 5, but capacity is only 4
+        let mut data = [0u8; 1];
+        data[0] = 5;
+
+        let error = HeaplessU8PrefixVec::<u32, 4>::try_from_slice(&data).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        let mut values: HeaplessVec<u32, 4> = HeaplessVec::new();
+        values.extend([1, 2, 3]);
+        let source = HeaplessU8PrefixVec::<u32, 4>(values);
+
+        let mut data = [0u8; 13];
+        source.serialize(&mut data.as_mut_slice()).unwrap();
+
+        assert_eq!(source.serialized_len(), data.len());
+        assert_eq!(HeaplessU8PrefixVec::<u32, 4>::PREFIX_LEN, 1);
+    }
+}
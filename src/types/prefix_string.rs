@@ -6,13 +6,19 @@ use std::fmt::Debug;
 use std::io::Write;
 use std::ops::Deref;
 
+use crate::types::serialized_len::SerializedLen;
+
 /// Macro to automate the generation of `PrefixString` types.
+///
+/// The byte order used for the length prefix is a macro argument (the
+/// `$to_bytes`/`$from_bytes` pair, e.g. `to_le_bytes`/`from_le_bytes`), so
+/// the same body backs both the little-endian and big-endian families.
 macro_rules! prefix_string_types {
-    ( ($n:tt, $p:tt), $(($name:tt, $prefix:tt)),+ ) => {
-        prefix_string_types!(($n, $p));
-        prefix_string_types!($( ($name, $prefix) ),+);
+    ( ($n:tt, $p:tt, $to:tt, $from:tt), $(($name:tt, $prefix:tt, $to_rest:tt, $from_rest:tt)),+ ) => {
+        prefix_string_types!(($n, $p, $to, $from));
+        prefix_string_types!($( ($name, $prefix, $to_rest, $from_rest) ),+);
     };
-    ( ($name:tt, $prefix_type:tt) ) => {
+    ( ($name:tt, $prefix_type:tt, $to_bytes:tt, $from_bytes:tt) ) => {
         /// A string prefixed by "custom" length type.
         #[derive(Clone, Eq, PartialEq)]
         #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -42,9 +48,9 @@ macro_rules! prefix_string_types {
         {
             fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
                 // read the length of the String
-                let mut buffer = vec![0u8; std::mem::size_of::<$prefix_type>()];
+                let mut buffer = [0u8; std::mem::size_of::<$prefix_type>()];
                 reader.read_exact(&mut buffer)?;
-                let length = $prefix_type::deserialize(&mut buffer.as_slice())? as usize;
+                let length = $prefix_type::$from_bytes(buffer) as usize;
 
                 let mut buffer = vec![0u8; length];
                 reader.read_exact(&mut buffer)?;
@@ -72,21 +78,36 @@ macro_rules! prefix_string_types {
                     ));
                 }
                 // add the length prefix
-                $prefix_type::serialize(&(self.0.len() as $prefix_type), writer)?;
+                writer.write_all(&$prefix_type::$to_bytes(self.0.len() as $prefix_type))?;
                 // serialize the string (without its "natural" prefix)
                 writer.write_all(self.0.as_bytes())?;
 
                 Ok(())
             }
         }
+
+        impl SerializedLen for $name {
+            const PREFIX_LEN: usize = std::mem::size_of::<$prefix_type>();
+
+            fn serialized_len(&self) -> usize {
+                Self::PREFIX_LEN + self.0.len()
+            }
+        }
     };
 }
 
 // Generate the prefix vec types.
 prefix_string_types!(
-    (U8PrefixString, u8),
-    (U16PrefixString, u16),
-    (U64PrefixString, u64)
+    (U8PrefixString, u8, to_le_bytes, from_le_bytes),
+    (U16PrefixString, u16, to_le_bytes, from_le_bytes),
+    (U64PrefixString, u64, to_le_bytes, from_le_bytes)
+);
+
+// Network byte order (big-endian) variants, e.g. for cross-chain wire
+// formats such as VAA-style messages.
+prefix_string_types!(
+    (U16BePrefixString, u16, to_be_bytes, from_be_bytes),
+    (U64BePrefixString, u64, to_be_bytes, from_be_bytes)
 );
 
 #[cfg(test)]
@@ -178,4 +199,32 @@ mod tests {
 
         assert_eq!(error.kind(), ErrorKind::InvalidData);
     }
+
+    #[test]
+    fn big_endian_round_trip() {
+        let string = String::from("string");
+        let source = U16BePrefixString(string);
+
+        let mut data = [0u8; 8];
+        source.serialize(&mut data.as_mut_slice()).unwrap();
+
+        // the length prefix is written in network byte order
+        assert_eq!(&data[0..2], u16::to_be_bytes(6).as_slice());
+
+        let restored = U16BePrefixString::try_from_slice(&data).unwrap();
+
+        assert_eq!(restored.len(), source.len());
+        assert_eq!(*restored, *source);
+    }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        let source = U16PrefixString(String::from("string"));
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        assert_eq!(source.serialized_len(), data.len());
+        assert_eq!(U16PrefixString::PREFIX_LEN, 2);
+    }
 }
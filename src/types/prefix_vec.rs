@@ -5,13 +5,24 @@ use std::ops::{Deref, DerefMut};
 use borsh::maybestd::io::Read;
 use borsh::{BorshDeserialize, BorshSerialize};
 
+use crate::types::serialized_len::{borsh_len, SerializedLen};
+
+/// Upper bound on how many elements are pre-allocated based on a declared
+/// (and as yet unverified) length prefix.
+const MAX_PREALLOCATION: usize = 4096;
+
 /// Macro to automate the generation of `PrefixVec` types.
+///
+/// The byte order used for the length prefix is a macro argument (the
+/// `$to_bytes`/`$from_bytes` pair, e.g. `to_le_bytes`/`from_le_bytes`), so
+/// the same body backs both the little-endian and big-endian families. The
+/// elements themselves are always serialized with borsh.
 macro_rules! prefix_vec_types {
-    ( ($n:tt, $p:tt), $(($name:tt, $prefix:tt)),+ ) => {
-        prefix_vec_types!(($n, $p));
-        prefix_vec_types!($( ($name, $prefix) ),+);
+    ( ($n:tt, $p:tt, $to:tt, $from:tt), $(($name:tt, $prefix:tt, $to_rest:tt, $from_rest:tt)),+ ) => {
+        prefix_vec_types!(($n, $p, $to, $from));
+        prefix_vec_types!($( ($name, $prefix, $to_rest, $from_rest) ),+);
     };
-    ( ($name:tt, $prefix_type:tt) ) => {
+    ( ($name:tt, $prefix_type:tt, $to_bytes:tt, $from_bytes:tt) ) => {
         /// A vector where the element data is prefixed by the vector length.
         #[derive(Clone, Eq, PartialEq)]
         #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -57,41 +68,21 @@ macro_rules! prefix_vec_types {
         {
             fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::maybestd::io::Result<Self> {
                 // read the length of the vec
-                let mut buffer = vec![0u8; std::mem::size_of::<$prefix_type>()];
+                let mut buffer = [0u8; std::mem::size_of::<$prefix_type>()];
                 reader.read_exact(&mut buffer)?;
-                let length = $prefix_type::deserialize(&mut buffer.as_slice())? as usize;
-
-                // buffer to read each item
-                let item_length = std::mem::size_of::<T>();
-                let mut buffer = vec![0u8; item_length];
-                // vec to store the items
-                let mut items: Vec<T> = Vec::with_capacity(length);
-
-                while items.len() < length {
-                    match reader.read(&mut buffer)? {
-                        0 => break,
-                        n if n == item_length => {
-                            items.push(T::deserialize(&mut buffer.as_slice())?)
-                        }
-                        e => {
-                            return Err(borsh::maybestd::io::Error::new(
-                                borsh::maybestd::io::ErrorKind::InvalidData,
-                                format!(
-                                    "unexpected number of bytes (read {e}, expected {item_length})"
-                                ),
-                            ))
-                        }
-                    }
-                }
-
-                if items.len() != length {
-                    return Err(borsh::maybestd::io::Error::new(
-                        borsh::maybestd::io::ErrorKind::InvalidData,
-                        format!(
-                            "unexpected vec length (read {}, expected {length})",
-                            items.len()
-                        ),
-                    ));
+                let length = $prefix_type::$from_bytes(buffer) as usize;
+
+                // a `$prefix_type` as wide as `u64` can declare a length far
+                // larger than the reader actually holds, so the declared
+                // length cannot be trusted as a pre-allocation size: cap the
+                // initial capacity and let the `Vec` grow as elements are
+                // actually read. items are deserialized directly (rather
+                // than via a generic borsh `Vec` impl) so variable-size `T`
+                // (e.g. `String`, nested prefix types, enums) round-trip
+                // correctly.
+                let mut items: Vec<T> = Vec::with_capacity(length.min(MAX_PREALLOCATION));
+                for _ in 0..length {
+                    items.push(T::deserialize_reader(reader)?);
                 }
 
                 Ok(Self(items))
@@ -114,7 +105,7 @@ macro_rules! prefix_vec_types {
                     ));
                 }
                 // add the length prefix
-                $prefix_type::serialize(&(self.0.len() as $prefix_type), writer)?;
+                writer.write_all(&$prefix_type::$to_bytes(self.0.len() as $prefix_type))?;
                 // serialize each item
                 for item in self.0.iter() {
                     item.serialize(writer)?;
@@ -123,14 +114,34 @@ macro_rules! prefix_vec_types {
                 Ok(())
             }
         }
+
+        impl<T> SerializedLen for $name<T>
+        where
+            T: BorshSerialize + BorshDeserialize,
+        {
+            const PREFIX_LEN: usize = std::mem::size_of::<$prefix_type>();
+
+            fn serialized_len(&self) -> usize {
+                Self::PREFIX_LEN
+                    + self.0.iter().map(|item| borsh_len(item)).sum::<usize>()
+            }
+        }
     };
 }
 
 prefix_vec_types!(
-    (U8PrefixVec, u8),
-    (U16PrefixVec, u16),
-    (U32PrefixVec, u32),
-    (U64PrefixVec, u64)
+    (U8PrefixVec, u8, to_le_bytes, from_le_bytes),
+    (U16PrefixVec, u16, to_le_bytes, from_le_bytes),
+    (U32PrefixVec, u32, to_le_bytes, from_le_bytes),
+    (U64PrefixVec, u64, to_le_bytes, from_le_bytes)
+);
+
+// Network byte order (big-endian) variants, e.g. for cross-chain wire
+// formats such as VAA-style messages.
+prefix_vec_types!(
+    (U16BePrefixVec, u16, to_be_bytes, from_be_bytes),
+    (U32BePrefixVec, u32, to_be_bytes, from_be_bytes),
+    (U64BePrefixVec, u64, to_be_bytes, from_be_bytes)
 );
 
 #[cfg(test)]
@@ -242,7 +253,9 @@ mod tests {
 
     #[test]
     fn fail_deserialize_invalid_data() {
-        // slices of bytes (3 u64 values) + 4 bytes
+        // declares a length of 5 items, but only 2 full items are present.
+        // borsh's own primitive deserializers remap a truncated read to
+        // `InvalidInput`, not `UnexpectedEof`.
         let mut data = [0u8; 28];
         data[0..8].copy_from_slice(u64::to_le_bytes(5).as_slice());
         data[8..16].copy_from_slice(u64::to_le_bytes(15).as_slice());
@@ -250,7 +263,20 @@ mod tests {
 
         let error = U64PrefixVec::<u64>::try_from_slice(&data).unwrap_err();
 
-        assert_eq!(error.kind(), borsh::maybestd::io::ErrorKind::InvalidData);
+        assert_eq!(error.kind(), borsh::maybestd::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn fail_deserialize_does_not_trust_a_huge_declared_length() {
+        // a declared length of `u64::MAX`, but the reader holds none of
+        // that data; this must fail fast rather than attempt a huge
+        // pre-allocation. borsh's own primitive deserializers remap a
+        // truncated read to `InvalidInput`, not `UnexpectedEof`.
+        let data = u64::to_le_bytes(u64::MAX);
+
+        let error = U64PrefixVec::<u64>::try_from_slice(&data).unwrap_err();
+
+        assert_eq!(error.kind(), borsh::maybestd::io::ErrorKind::InvalidInput);
     }
 
     #[test]
@@ -278,4 +304,70 @@ mod tests {
 
         assert_eq!(error.kind(), borsh::maybestd::io::ErrorKind::InvalidData);
     }
+
+    #[test]
+    fn big_endian_round_trip() {
+        let values = (0..10).collect::<Vec<u32>>();
+        let source = U16BePrefixVec::<u32>(values);
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        // the length prefix is written in network byte order
+        assert_eq!(&data[0..2], u16::to_be_bytes(10).as_slice());
+
+        let restored = U16BePrefixVec::<u32>::try_from_slice(&data).unwrap();
+
+        assert_eq!(restored.len(), source.len());
+        assert_eq!(restored.as_slice(), source.as_slice());
+    }
+
+    #[test]
+    fn round_trip_variable_size_element_string() {
+        let values = vec![
+            String::from("hello"),
+            String::from(""),
+            String::from("a much longer string value"),
+        ];
+        let source = U32PrefixVec::<String>(values);
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        let restored = U32PrefixVec::<String>::try_from_slice(&data).unwrap();
+
+        assert_eq!(restored.as_slice(), source.as_slice());
+    }
+
+    #[test]
+    fn round_trip_variable_size_element_nested_prefix_vec() {
+        let values = vec![
+            U8PrefixVec::<u8>(vec![1, 2, 3]),
+            U8PrefixVec::<u8>(vec![]),
+            U8PrefixVec::<u8>(vec![4]),
+        ];
+        let source = U32PrefixVec::<U8PrefixVec<u8>>(values);
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        let restored = U32PrefixVec::<U8PrefixVec<u8>>::try_from_slice(&data).unwrap();
+
+        assert_eq!(restored.len(), source.len());
+        for (restored_item, source_item) in restored.iter().zip(source.iter()) {
+            assert_eq!(restored_item.as_slice(), source_item.as_slice());
+        }
+    }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        let values = (0..10).collect::<Vec<u32>>();
+        let source = U16PrefixVec::<u32>(values);
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        assert_eq!(source.serialized_len(), data.len());
+        assert_eq!(U16PrefixVec::<u32>::PREFIX_LEN, 2);
+    }
 }
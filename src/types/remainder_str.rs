@@ -1,10 +1,12 @@
-use borsh::io::Read;
+use borsh::maybestd::io::Read;
 use borsh::{BorshDeserialize, BorshSerialize};
 use std::fmt::Debug;
 use std::io::Write;
 use std::ops::Deref;
 use std::str::FromStr;
 
+use crate::types::serialized_len::SerializedLen;
+
 /// A wrapped `str` type.
 ///
 /// This is useful for deserializing a string value that does not have
@@ -54,7 +56,7 @@ impl Debug for RemainderStr {
 }
 
 impl BorshDeserialize for RemainderStr {
-    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::maybestd::io::Result<Self> {
         let mut value: String = String::new();
         while let Ok(c) = u8::deserialize_reader(reader) {
             value.push(c as char);
@@ -64,7 +66,7 @@ impl BorshDeserialize for RemainderStr {
 }
 
 impl BorshSerialize for RemainderStr {
-    fn serialize<W: Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> borsh::maybestd::io::Result<()> {
         // serialize the bytes of the string without adding a prefix
         for c in self.0.as_bytes() {
             c.serialize(writer)?;
@@ -73,6 +75,15 @@ impl BorshSerialize for RemainderStr {
     }
 }
 
+impl SerializedLen for RemainderStr {
+    // there is no length prefix: the value consumes the rest of the buffer
+    const PREFIX_LEN: usize = 0;
+
+    fn serialized_len(&self) -> usize {
+        self.0.len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,4 +114,15 @@ mod tests {
         assert_eq!(restored.len(), source.len());
         assert_eq!(restored, source);
     }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        let source: RemainderStr = "this is a longer str".into();
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        assert_eq!(source.serialized_len(), data.len());
+        assert_eq!(RemainderStr::PREFIX_LEN, 0);
+    }
 }
@@ -0,0 +1,196 @@
+use borsh::maybestd::io::{Read, Result};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::fmt::Debug;
+use std::io::Write;
+use std::ops::{Deref, DerefMut};
+
+use crate::types::serialized_len::{borsh_len, SerializedLen};
+
+/// A wrapped `Vec<T>` type.
+///
+/// This is useful for deserializing a sequence of elements that does not
+/// have a length prefix: each `T` is read back-to-back until the reader
+/// reaches a clean EOF between elements. A reader that runs out of bytes
+/// (or holds malformed bytes) in the middle of an element is not treated as
+/// a valid end of the sequence: `T::deserialize_reader`'s error is
+/// propagated as-is, rather than being reinterpreted here, since borsh's
+/// own primitive and derived deserializers already report both cases
+/// through the same `ErrorKind`.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemainderVec<T: BorshSerialize + BorshDeserialize>(Vec<T>);
+
+impl<T> RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    pub fn from(value: Vec<T>) -> Self {
+        value.into()
+    }
+}
+
+impl<T> Deref for RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+
+/// `Debug` implementation.
+///
+/// This implementation simply forwards to the inner `Vec` type.
+impl<T> Debug for RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.0))
+    }
+}
+
+impl<T> BorshDeserialize for RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut items = Vec::new();
+
+        loop {
+            // peek a single byte to tell a clean EOF between elements apart
+            // from an EOF in the middle of one
+            let mut first_byte = [0u8; 1];
+            if reader.read(&mut first_byte)? == 0 {
+                break;
+            }
+
+            let mut element_reader = first_byte.as_slice().chain(&mut *reader);
+            let item = T::deserialize_reader(&mut element_reader)?;
+            items.push(item);
+        }
+
+        Ok(Self(items))
+    }
+}
+
+impl<T> BorshSerialize for RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        // serialize each item back-to-back, without a length prefix
+        for item in self.0.iter() {
+            item.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> SerializedLen for RemainderVec<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    // there is no length prefix: the value consumes the rest of the buffer
+    const PREFIX_LEN: usize = 0;
+
+    fn serialized_len(&self) -> usize {
+        self.0.iter().map(|item| borsh_len(item)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_data() {
+        let mut data = [0u8; 12];
+        data[0..4].copy_from_slice(u32::to_le_bytes(1).as_slice());
+        data[4..8].copy_from_slice(u32::to_le_bytes(2).as_slice());
+        data[8..12].copy_from_slice(u32::to_le_bytes(3).as_slice());
+
+        let vec = RemainderVec::<u32>::try_from_slice(&data).unwrap();
+
+        assert_eq!(vec.as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn deserialize_empty_data() {
+        let vec = RemainderVec::<u32>::try_from_slice(&[]).unwrap();
+
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn serialize_data() {
+        let source = RemainderVec::from(vec![1u32, 2, 3]);
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        let restored = RemainderVec::<u32>::try_from_slice(&data).unwrap();
+
+        assert_eq!(restored.as_slice(), source.as_slice());
+    }
+
+    #[test]
+    fn fail_deserialize_eof_in_the_middle_of_an_element() {
+        // 2 full u32 values, followed by 2 trailing bytes: not enough for a
+        // third element, so this must be rejected rather than silently
+        // truncated. borsh's own primitive deserializers remap a truncated
+        // read to `InvalidInput`, not `UnexpectedEof`.
+        let mut data = [0u8; 10];
+        data[0..4].copy_from_slice(u32::to_le_bytes(1).as_slice());
+        data[4..8].copy_from_slice(u32::to_le_bytes(2).as_slice());
+
+        let error = RemainderVec::<u32>::try_from_slice(&data).unwrap_err();
+
+        assert_eq!(error.kind(), borsh::maybestd::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn fail_deserialize_propagates_element_errors_unchanged() {
+        // a single, fully-present byte that is simply not a valid `bool`
+        // representation: the error from `bool::deserialize_reader` itself
+        // must come back unmodified, not wrapped or reinterpreted
+        let data = [2u8];
+
+        let direct_error = bool::try_from_slice(&data).unwrap_err();
+        let vec_error = RemainderVec::<bool>::try_from_slice(&data).unwrap_err();
+
+        assert_eq!(vec_error.kind(), direct_error.kind());
+        assert_eq!(vec_error.to_string(), direct_error.to_string());
+    }
+
+    #[test]
+    fn serialized_len_matches_actual_serialized_size() {
+        let source = RemainderVec::from(vec![1u32, 2, 3]);
+
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+
+        assert_eq!(source.serialized_len(), data.len());
+        assert_eq!(RemainderVec::<u32>::PREFIX_LEN, 0);
+    }
+}
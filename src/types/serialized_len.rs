@@ -0,0 +1,58 @@
+use borsh::maybestd::io::{Result, Write};
+use borsh::BorshSerialize;
+
+/// A type whose exact serialized (wire) size can be computed without
+/// serializing it into a buffer.
+///
+/// This lets callers pre-allocate an exact buffer before calling
+/// `serialize`, and lets account-size math for fixed layouts be done
+/// statically.
+pub trait SerializedLen {
+    /// Number of bytes used by the length prefix, if any. For a prefix whose
+    /// width depends on the length (the `Compact*` types), this is the
+    /// smallest width.
+    const PREFIX_LEN: usize;
+
+    /// Returns the exact number of bytes `self` will occupy once serialized.
+    fn serialized_len(&self) -> usize;
+}
+
+/// A `Write` sink that only counts the bytes written to it, used to measure
+/// a value's serialized size without allocating a buffer for it.
+struct CountingWriter(usize);
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns the number of bytes `value` will occupy once serialized, without
+/// allocating a buffer to hold the serialized bytes.
+pub(crate) fn borsh_len<T: BorshSerialize + ?Sized>(value: &T) -> usize {
+    let mut writer = CountingWriter(0);
+    value
+        .serialize(&mut writer)
+        .expect("serializing into a counting writer cannot fail");
+    writer.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borsh_len_matches_actual_serialized_size() {
+        let value = vec![1u32, 2, 3, 4];
+
+        let mut data = Vec::new();
+        value.serialize(&mut data).unwrap();
+
+        assert_eq!(borsh_len(&value), data.len());
+    }
+}